@@ -0,0 +1,155 @@
+//! serve a static site directly out of a zip archive, without unpacking it to disk
+
+use std::collections::{BTreeSet, HashMap};
+use std::io::{Cursor, Read};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use mime_guess::MimeGuess;
+use salvo_core::http::header::{ACCEPT, CONTENT_TYPE};
+use salvo_core::http::{HeaderValue, StatusError};
+use salvo_core::{async_trait, Depot, FlowCtrl, Handler, Request, Response};
+use zip::ZipArchive;
+
+use super::dir::render_listing;
+use super::format_url_path_safely;
+
+/// `StaticArchive` mounts the contents of a zip archive and serves individual entries over HTTP
+/// without unpacking it to disk. The central directory is parsed once, when the archive is
+/// opened, into an in-memory index from entry name to its index within it; the parsed archive
+/// itself is kept around so serving an entry never re-parses the central directory.
+#[derive(Clone)]
+pub struct StaticArchive {
+    archive: Arc<Mutex<ZipArchive<Cursor<Vec<u8>>>>>,
+    index: Arc<HashMap<String, usize>>,
+    defaults: Vec<String>,
+}
+
+impl std::fmt::Debug for StaticArchive {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StaticArchive")
+            .field("entries", &self.index.len())
+            .field("defaults", &self.defaults)
+            .finish()
+    }
+}
+
+impl StaticArchive {
+    /// Open the zip archive at `path` and index its entries.
+    pub fn new(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Self::from_bytes(std::fs::read(path)?)
+    }
+
+    /// Index a zip archive already loaded into memory.
+    pub fn from_bytes(data: impl Into<Vec<u8>>) -> std::io::Result<Self> {
+        let mut archive = ZipArchive::new(Cursor::new(data.into())).map_err(to_io_error)?;
+        let mut index = HashMap::with_capacity(archive.len());
+        for i in 0..archive.len() {
+            let entry = archive.by_index(i).map_err(to_io_error)?;
+            if !entry.is_dir() {
+                index.insert(entry.name().to_owned(), i);
+            }
+        }
+        Ok(Self {
+            archive: Arc::new(Mutex::new(archive)),
+            index: Arc::new(index),
+            defaults: vec!["index.html".to_owned()],
+        })
+    }
+
+    /// File names tried, in order, when a request resolves to a virtual directory. Defaults to
+    /// `["index.html"]`.
+    pub fn defaults(mut self, defaults: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.defaults = defaults.into_iter().map(Into::into).collect();
+        self
+    }
+
+    fn read_entry(&self, name: &str) -> Option<Vec<u8>> {
+        let index = *self.index.get(name)?;
+        let mut archive = self.archive.lock().ok()?;
+        let mut entry = archive.by_index(index).ok()?;
+        let mut buf = Vec::with_capacity(entry.size() as usize);
+        entry.read_to_end(&mut buf).ok()?;
+        Some(buf)
+    }
+
+    fn is_virtual_dir(&self, prefix: &str) -> bool {
+        let prefix = dir_prefix(prefix);
+        self.index.keys().any(|name| name.starts_with(&prefix))
+    }
+
+    fn list_virtual_dir(&self, prefix: &str) -> Vec<String> {
+        let prefix = dir_prefix(prefix);
+        let mut names = BTreeSet::new();
+        for name in self.index.keys() {
+            if let Some(rest) = name.strip_prefix(&prefix) {
+                if let Some(first) = rest.split('/').next() {
+                    if !first.is_empty() {
+                        names.insert(first.to_owned());
+                    }
+                }
+            }
+        }
+        names.into_iter().collect()
+    }
+
+    fn send_entry(&self, name: &str, res: &mut Response) -> bool {
+        let Some(data) = self.read_entry(name) else {
+            return false;
+        };
+        let mime = MimeGuess::from_path(name).first_or_octet_stream();
+        res.headers_mut()
+            .insert(CONTENT_TYPE, HeaderValue::from_str(mime.as_ref()).unwrap());
+        res.write_body(data).ok();
+        true
+    }
+}
+
+fn dir_prefix(path: &str) -> String {
+    if path.is_empty() {
+        String::new()
+    } else {
+        format!("{path}/")
+    }
+}
+
+fn to_io_error(err: zip::result::ZipError) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, err)
+}
+
+#[async_trait]
+impl Handler for StaticArchive {
+    async fn handle(&self, req: &mut Request, _depot: &mut Depot, res: &mut Response, _ctrl: &mut FlowCtrl) {
+        let rel_path = req.param::<String>("**path").unwrap_or_default();
+        let rel_path = format_url_path_safely(&rel_path);
+
+        if self.index.contains_key(&rel_path) && self.send_entry(&rel_path, res) {
+            return;
+        }
+
+        for default in &self.defaults {
+            let candidate = if rel_path.is_empty() {
+                default.clone()
+            } else {
+                format!("{rel_path}/{default}")
+            };
+            if self.send_entry(&candidate, res) {
+                return;
+            }
+        }
+
+        if rel_path.is_empty() || self.is_virtual_dir(&rel_path) {
+            let names = self.list_virtual_dir(&rel_path);
+            let accept = req
+                .headers()
+                .get(ACCEPT)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or("text/plain")
+                .to_owned();
+            render_listing(names, &accept, res);
+            return;
+        }
+
+        res.render(StatusError::not_found());
+    }
+}
@@ -0,0 +1,133 @@
+//! conditional-request (`ETag` / `Last-Modified`) evaluation shared by the static handlers
+
+use std::fmt::Write as _;
+use std::time::{Duration, SystemTime};
+
+use salvo_core::http::header::{IF_MATCH, IF_MODIFIED_SINCE, IF_NONE_MATCH, IF_UNMODIFIED_SINCE};
+use salvo_core::http::Request;
+
+/// Outcome of evaluating a request's conditional headers against a resource's validators.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Precondition {
+    /// No conditional header applies (or it's satisfied); serve the response normally.
+    Proceed,
+    /// The resource is unchanged; respond `304 Not Modified` with no body.
+    NotModified,
+    /// A precondition failed; respond `412 Precondition Failed`.
+    Failed,
+}
+
+/// A weak validator derived from a resource's size and modification time. Good enough to detect
+/// changes without hashing file contents on every request.
+pub(crate) fn etag(len: u64, modified: Option<SystemTime>) -> String {
+    let mtime = modified
+        .and_then(|time| time.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    format!("W/\"{len:x}-{mtime:x}\"")
+}
+
+/// A strong validator derived from a resource's exact content hash, for resources where hashing
+/// is cheap (e.g. assets already hashed at compile time). Unlike [`etag`], this only matches
+/// another resource with identical content.
+pub(crate) fn etag_from_hash(hash: &[u8]) -> String {
+    let mut hex = String::with_capacity(hash.len() * 2);
+    for byte in hash {
+        let _ = write!(hex, "{byte:02x}");
+    }
+    format!("\"{hex}\"")
+}
+
+/// HTTP-dates have one-second resolution; truncate `time` to whole seconds so a resource whose
+/// `modified` has sub-second precision still compares equal to the value a client echoed back
+/// from our (also-truncated) `Last-Modified` header.
+pub(crate) fn truncate_to_secs(time: SystemTime) -> SystemTime {
+    let secs = time
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+fn etag_list_matches(header: &str, etag: &str) -> bool {
+    let bare = etag.trim_start_matches("W/");
+    header.trim() == "*"
+        || header
+            .split(',')
+            .map(str::trim)
+            .any(|candidate| candidate == etag || candidate.trim_start_matches("W/") == bare)
+}
+
+/// Evaluate `If-Match`/`If-Unmodified-Since` and `If-None-Match`/`If-Modified-Since` against
+/// `etag`/`modified`, following the precedence order of RFC 7232 (match-style headers win over
+/// their date-style counterpart when both are present).
+pub(crate) fn evaluate(req: &Request, etag: &str, modified: Option<SystemTime>) -> Precondition {
+    if let Some(if_match) = req.headers().get(IF_MATCH).and_then(|v| v.to_str().ok()) {
+        if !etag_list_matches(if_match, etag) {
+            return Precondition::Failed;
+        }
+    } else if let Some(since) = req.headers().get(IF_UNMODIFIED_SINCE).and_then(|v| v.to_str().ok()) {
+        if let (Ok(since), Some(modified)) = (httpdate::parse_http_date(since), modified) {
+            if truncate_to_secs(modified) > since {
+                return Precondition::Failed;
+            }
+        }
+    }
+
+    if let Some(if_none_match) = req.headers().get(IF_NONE_MATCH).and_then(|v| v.to_str().ok()) {
+        if etag_list_matches(if_none_match, etag) {
+            return Precondition::NotModified;
+        }
+    } else if let Some(since) = req.headers().get(IF_MODIFIED_SINCE).and_then(|v| v.to_str().ok()) {
+        if let (Ok(since), Some(modified)) = (httpdate::parse_http_date(since), modified) {
+            if truncate_to_secs(modified) <= since {
+                return Precondition::NotModified;
+            }
+        }
+    }
+
+    Precondition::Proceed
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn etag_is_weak_and_hex_encoded() {
+        let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(0x10);
+        assert_eq!(etag(0x20, Some(modified)), "W/\"20-10\"");
+        assert_eq!(etag(0x20, None), "W/\"20-0\"");
+    }
+
+    #[test]
+    fn etag_from_hash_is_strong_and_lowercase_hex() {
+        assert_eq!(etag_from_hash(&[0xde, 0xad, 0xbe, 0xef]), "\"deadbeef\"");
+        assert_eq!(etag_from_hash(&[]), "\"\"");
+    }
+
+    #[test]
+    fn truncate_to_secs_drops_subsecond_precision() {
+        let time = SystemTime::UNIX_EPOCH + Duration::from_millis(1_500);
+        assert_eq!(truncate_to_secs(time), SystemTime::UNIX_EPOCH + Duration::from_secs(1));
+    }
+
+    #[test]
+    fn etag_list_matches_wildcard() {
+        assert!(etag_list_matches("*", "\"abc\""));
+    }
+
+    #[test]
+    fn etag_list_matches_exact_and_list() {
+        assert!(etag_list_matches("\"abc\", \"def\"", "\"def\""));
+        assert!(!etag_list_matches("\"abc\", \"def\"", "\"ghi\""));
+    }
+
+    #[test]
+    fn etag_list_matches_weak_and_strong_forms_interchangeably() {
+        assert!(etag_list_matches("W/\"abc\"", "\"abc\""));
+        assert!(etag_list_matches("\"abc\"", "W/\"abc\""));
+    }
+}
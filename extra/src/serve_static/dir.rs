@@ -0,0 +1,203 @@
+//! static dir handler
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use salvo_core::http::header::ACCEPT;
+use salvo_core::http::StatusError;
+use salvo_core::{async_trait, Depot, FlowCtrl, Handler, Request, Response};
+
+use super::encoding::{resolve_precompressed, ContentCoding};
+use super::file::send_file;
+use super::format_url_path_safely;
+
+/// Options that control how [`StaticDir`] resolves and lists directories.
+#[derive(Clone, Debug)]
+pub struct StaticDirOptions {
+    /// Serve files and directories whose name starts with a dot. Defaults to `false`.
+    pub dot_files: bool,
+    /// Render a directory listing when no default file is found. Defaults to `false`.
+    pub listing: bool,
+    /// File names tried, in order, when a request resolves to a directory.
+    pub defaults: Vec<String>,
+}
+
+impl Default for StaticDirOptions {
+    fn default() -> Self {
+        Self {
+            dot_files: false,
+            listing: false,
+            defaults: Vec::new(),
+        }
+    }
+}
+
+/// `StaticDir` is a handler that serves files out of one or more directories, falling through
+/// to the next directory in the list when the current one doesn't contain a match.
+#[derive(Clone)]
+pub struct StaticDir {
+    dirs: Vec<PathBuf>,
+    options: StaticDirOptions,
+    precompressed: Vec<ContentCoding>,
+    fallback_handler: Option<Arc<dyn Handler>>,
+}
+
+impl std::fmt::Debug for StaticDir {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StaticDir")
+            .field("dirs", &self.dirs)
+            .field("options", &self.options)
+            .field("precompressed", &self.precompressed)
+            .field("fallback_handler", &self.fallback_handler.is_some())
+            .finish()
+    }
+}
+
+impl StaticDir {
+    /// Create a new `StaticDir` serving `dirs` with default options.
+    pub fn new<T: Into<PathBuf>>(dirs: impl IntoIterator<Item = T>) -> Self {
+        Self::width_options(dirs, StaticDirOptions::default())
+    }
+
+    /// Create a new `StaticDir` serving `dirs` with the given `options`.
+    pub fn width_options<T: Into<PathBuf>>(dirs: impl IntoIterator<Item = T>, options: StaticDirOptions) -> Self {
+        Self {
+            dirs: dirs.into_iter().map(Into::into).collect(),
+            options,
+            precompressed: Vec::new(),
+            fallback_handler: None,
+        }
+    }
+
+    /// Enable serving precompressed sibling files (`<path>.br`, `<path>.gz`, `<path>.zst`, ...)
+    /// when the request's `Accept-Encoding` header allows it and the sibling exists on disk.
+    pub fn precompressed(mut self, codings: impl IntoIterator<Item = ContentCoding>) -> Self {
+        self.precompressed.extend(codings);
+        self
+    }
+
+    /// Run `handler` instead of responding `404` when no file or directory listing matches the
+    /// request. The handler receives the original request unchanged, so it can inspect the
+    /// attempted path (e.g. to render a styled error page, or route SPA paths to `index.html`).
+    pub fn with_fallback_handler(mut self, handler: impl Handler) -> Self {
+        self.fallback_handler = Some(Arc::new(handler));
+        self
+    }
+}
+
+fn is_dot_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| name.starts_with('.'))
+        .unwrap_or(false)
+}
+
+#[async_trait]
+impl Handler for StaticDir {
+    async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+        let rel_path = req.param::<String>("**path").unwrap_or_default();
+        let rel_path = format_url_path_safely(&rel_path);
+
+        for dir in &self.dirs {
+            let path = dir.join(&rel_path);
+            if !self.options.dot_files && is_dot_file(&path) {
+                continue;
+            }
+            if path.is_dir() {
+                for default in &self.options.defaults {
+                    let default_path = path.join(default);
+                    if default_path.is_file() {
+                        let (serve_path, coding) = resolve_precompressed(&default_path, &self.precompressed, req);
+                        send_file(
+                            &default_path,
+                            &serve_path,
+                            coding,
+                            !self.precompressed.is_empty(),
+                            None,
+                            None,
+                            req,
+                            res,
+                        )
+                        .await;
+                        return;
+                    }
+                }
+                if self.options.listing {
+                    render_dir_listing(&path, req, res);
+                    return;
+                }
+            } else if path.is_file() {
+                let (serve_path, coding) = resolve_precompressed(&path, &self.precompressed, req);
+                send_file(
+                    &path,
+                    &serve_path,
+                    coding,
+                    !self.precompressed.is_empty(),
+                    None,
+                    None,
+                    req,
+                    res,
+                )
+                .await;
+                return;
+            }
+        }
+
+        match &self.fallback_handler {
+            Some(handler) => handler.handle(req, depot, res, ctrl).await,
+            None => res.render(StatusError::not_found()),
+        }
+    }
+}
+
+fn render_dir_listing(dir: &Path, req: &Request, res: &mut Response) {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+
+    let accept = req
+        .headers()
+        .get(ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("text/plain")
+        .to_owned();
+
+    render_listing(names, &accept, res);
+}
+
+/// Render a directory listing from a plain list of entry names, negotiating the representation
+/// from `accept` the same way disk-backed [`StaticDir`] listings do. Shared with
+/// [`crate::serve_static::archive::StaticArchive`] so archive-backed directories render
+/// identically to on-disk ones.
+pub(crate) fn render_listing(names: Vec<String>, accept: &str, res: &mut Response) {
+    if accept.contains("application/json") {
+        let items = names
+            .iter()
+            .map(|name| format!("\"{name}\""))
+            .collect::<Vec<_>>()
+            .join(",");
+        res.render(format!("{{\"files\":[{items}]}}"));
+    } else if accept.contains("text/xml") {
+        let items = names
+            .iter()
+            .map(|name| format!("<item>{name}</item>"))
+            .collect::<Vec<_>>()
+            .join("");
+        res.render(format!("<list>{items}</list>"));
+    } else if accept.contains("text/html") {
+        let items = names
+            .iter()
+            .map(|name| format!("<li>{name}</li>"))
+            .collect::<Vec<_>>()
+            .join("");
+        res.render(format!("<html><body><ul>{items}</ul></body></html>"));
+    } else {
+        res.render(names.join("\n"));
+    }
+}
@@ -0,0 +1,86 @@
+//! `Content-Disposition` header construction for file responses
+
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+
+/// Whether a file response should be rendered by the browser (`inline`, the default for
+/// ordinary web assets) or downloaded as an attachment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContentDisposition {
+    /// Render the asset in the browser.
+    Inline,
+    /// Prompt the browser to download the asset.
+    Attachment,
+}
+
+impl ContentDisposition {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Inline => "inline",
+            Self::Attachment => "attachment",
+        }
+    }
+}
+
+/// Build a `Content-Disposition` header value, optionally naming `filename`: ASCII-sanitized for
+/// the legacy `filename=` parameter, plus an RFC 5987 `filename*=UTF-8''<percent-encoded>`
+/// parameter when `filename` contains non-ASCII characters.
+pub(crate) fn header_value(disposition: ContentDisposition, filename: Option<&str>) -> String {
+    let Some(filename) = filename else {
+        return disposition.as_str().to_owned();
+    };
+    let ascii_filename: String = filename
+        .chars()
+        .map(|c| {
+            if c.is_ascii() && !c.is_ascii_control() && c != '"' && c != '\\' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    let mut value = format!("{}; filename=\"{ascii_filename}\"", disposition.as_str());
+    if !filename.is_ascii() {
+        value.push_str("; filename*=UTF-8''");
+        value.push_str(&utf8_percent_encode(filename, NON_ALPHANUMERIC).to_string());
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_filename_is_bare() {
+        assert_eq!(header_value(ContentDisposition::Inline, None), "inline");
+        assert_eq!(header_value(ContentDisposition::Attachment, None), "attachment");
+    }
+
+    #[test]
+    fn ascii_filename_only_sets_legacy_parameter() {
+        assert_eq!(
+            header_value(ContentDisposition::Attachment, Some("report.pdf")),
+            "attachment; filename=\"report.pdf\""
+        );
+    }
+
+    #[test]
+    fn control_characters_are_replaced() {
+        let value = header_value(ContentDisposition::Inline, Some("evil\r\nfile\t.txt"));
+        assert_eq!(value, "inline; filename=\"evil__file_.txt\"");
+    }
+
+    #[test]
+    fn quote_and_backslash_are_replaced() {
+        let value = header_value(ContentDisposition::Inline, Some("a\"b\\c"));
+        assert_eq!(value, "inline; filename=\"a_b_c\"");
+    }
+
+    #[test]
+    fn non_ascii_adds_rfc5987_parameter() {
+        // `NON_ALPHANUMERIC` percent-encodes everything but ASCII letters/digits, including `.`.
+        let value = header_value(ContentDisposition::Inline, Some("caf\u{e9}.txt"));
+        assert_eq!(value, "inline; filename=\"caf_.txt\"; filename*=UTF-8''caf%C3%A9%2Etxt");
+    }
+}
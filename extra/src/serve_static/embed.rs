@@ -0,0 +1,250 @@
+//! embedded static assets handler
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use rust_embed::{EmbeddedFile, RustEmbed};
+use salvo_core::http::header::{
+    ACCEPT_RANGES, CONTENT_DISPOSITION, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, ETAG, IF_RANGE, LAST_MODIFIED,
+    RANGE,
+};
+use salvo_core::http::{HeaderValue, StatusCode, StatusError};
+use salvo_core::{async_trait, Depot, FlowCtrl, Handler, Request, Response};
+
+use super::conditional::{self, Precondition};
+use super::disposition::{self, ContentDisposition};
+use super::format_url_path_safely;
+use super::range::HttpRange;
+
+/// Extension trait letting a `rust_embed` [`EmbeddedFile`] be rendered as a response, or turned
+/// into a standalone [`Handler`].
+pub trait EmbeddedFileExt {
+    /// Render this embedded file into `res`.
+    fn render(self, req: &mut Request, res: &mut Response);
+    /// Turn this embedded file into a `Handler` that always serves it.
+    fn into_handler(self) -> EmbeddedFileHandler;
+}
+
+impl EmbeddedFileExt for EmbeddedFile {
+    fn render(self, req: &mut Request, res: &mut Response) {
+        render_embedded_file(self, req, res);
+    }
+
+    fn into_handler(self) -> EmbeddedFileHandler {
+        EmbeddedFileHandler {
+            file: self,
+            disposition: ContentDisposition::Inline,
+            download_name: None,
+        }
+    }
+}
+
+/// A `Handler` that always serves one pre-resolved [`EmbeddedFile`].
+#[derive(Clone)]
+pub struct EmbeddedFileHandler {
+    file: EmbeddedFile,
+    disposition: ContentDisposition,
+    download_name: Option<String>,
+}
+
+impl EmbeddedFileHandler {
+    /// Respond with `Content-Disposition: attachment`, so browsers download the file instead of
+    /// rendering it.
+    pub fn content_disposition_attachment(mut self) -> Self {
+        self.disposition = ContentDisposition::Attachment;
+        self
+    }
+
+    /// Respond with `Content-Disposition: inline` (the default), so browsers render the file
+    /// when they can.
+    pub fn content_disposition_inline(mut self) -> Self {
+        self.disposition = ContentDisposition::Inline;
+        self
+    }
+
+    /// Set the file name advertised in `Content-Disposition`. When it is not set, the header
+    /// carries no file name.
+    pub fn download_name(mut self, name: impl Into<String>) -> Self {
+        self.download_name = Some(name.into());
+        self
+    }
+}
+
+#[async_trait]
+impl Handler for EmbeddedFileHandler {
+    async fn handle(&self, req: &mut Request, _depot: &mut Depot, res: &mut Response, _ctrl: &mut FlowCtrl) {
+        let download_name = self.download_name.clone();
+        render_embedded_file_as(self.file.clone(), Some((self.disposition, download_name)), req, res);
+    }
+}
+
+/// Write an embedded file's content type and body into `res`, evaluating conditional
+/// (`ETag`/`Last-Modified`) headers and honoring a `Range` request (with `If-Range`) against its
+/// in-memory bytes so embedded assets are seekable and cacheable too.
+pub fn render_embedded_file(file: EmbeddedFile, req: &mut Request, res: &mut Response) {
+    render_embedded_file_as(file, None, req, res);
+}
+
+fn render_embedded_file_as(
+    file: EmbeddedFile,
+    disposition: Option<(ContentDisposition, Option<String>)>,
+    req: &mut Request,
+    res: &mut Response,
+) {
+    let mime = file.metadata.mimetype();
+    let data = file.data;
+    let full_len = data.len() as u64;
+    let modified = file
+        .metadata
+        .last_modified()
+        .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+    let etag = conditional::etag_from_hash(&file.metadata.sha256_hash());
+
+    let headers = res.headers_mut();
+    headers.insert(ETAG, HeaderValue::from_str(&etag).unwrap());
+    if let Some(modified) = modified {
+        headers.insert(
+            LAST_MODIFIED,
+            HeaderValue::from_str(&httpdate::fmt_http_date(modified)).unwrap(),
+        );
+    }
+
+    match conditional::evaluate(req, &etag, modified) {
+        Precondition::Failed => {
+            res.status_code(StatusCode::PRECONDITION_FAILED);
+            return;
+        }
+        Precondition::NotModified => {
+            res.status_code(StatusCode::NOT_MODIFIED);
+            return;
+        }
+        Precondition::Proceed => {}
+    }
+
+    let headers = res.headers_mut();
+    headers.insert(
+        CONTENT_TYPE,
+        HeaderValue::from_str(mime).unwrap_or_else(|_| HeaderValue::from_static("application/octet-stream")),
+    );
+    headers.insert(ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    if let Some((disposition, name)) = &disposition {
+        if let Ok(value) = HeaderValue::from_str(&disposition::header_value(*disposition, name.as_deref())) {
+            headers.insert(CONTENT_DISPOSITION, value);
+        }
+    }
+
+    let range_header = req
+        .headers()
+        .get(RANGE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_owned);
+    let honor_range = range_header.is_some()
+        && req
+            .headers()
+            .get(IF_RANGE)
+            .and_then(|value| value.to_str().ok())
+            .map(|if_range| super::file::if_range_matches(if_range, &etag, modified))
+            .unwrap_or(true);
+
+    let range = match (honor_range, range_header) {
+        (true, Some(header)) => match HttpRange::parse(&header, full_len) {
+            Ok(ranges) => Some(ranges[0]),
+            Err(_) => {
+                res.headers_mut().insert(
+                    CONTENT_RANGE,
+                    HeaderValue::from_str(&format!("bytes */{full_len}")).unwrap(),
+                );
+                res.status_code(StatusCode::RANGE_NOT_SATISFIABLE);
+                return;
+            }
+        },
+        _ => None,
+    };
+
+    if let Some(range) = range {
+        let start = range.start as usize;
+        let end = start + range.length as usize;
+        let headers = res.headers_mut();
+        headers.insert(
+            CONTENT_RANGE,
+            HeaderValue::from_str(&format!("bytes {}-{}/{full_len}", range.start, range.start + range.length - 1))
+                .unwrap(),
+        );
+        headers.insert(CONTENT_LENGTH, HeaderValue::from_str(&range.length.to_string()).unwrap());
+        res.status_code(StatusCode::PARTIAL_CONTENT);
+        res.write_body(data[start..end].to_vec()).ok();
+    } else {
+        res.headers_mut()
+            .insert(CONTENT_LENGTH, HeaderValue::from_str(&full_len.to_string()).unwrap());
+        res.write_body(data.into_owned()).ok();
+    }
+}
+
+/// `StaticEmbed` serves the contents of a type deriving `RustEmbed`.
+#[derive(Clone)]
+pub struct StaticEmbed<E: RustEmbed> {
+    fallback: Option<String>,
+    fallback_handler: Option<Arc<dyn Handler>>,
+    _assets: PhantomData<E>,
+}
+
+impl<E: RustEmbed> std::fmt::Debug for StaticEmbed<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StaticEmbed")
+            .field("fallback", &self.fallback)
+            .field("fallback_handler", &self.fallback_handler.is_some())
+            .finish()
+    }
+}
+
+/// Create a handler that serves the folder embedded in `E`.
+pub fn static_embed<E: RustEmbed>() -> StaticEmbed<E> {
+    StaticEmbed {
+        fallback: None,
+        fallback_handler: None,
+        _assets: PhantomData,
+    }
+}
+
+impl<E: RustEmbed> StaticEmbed<E> {
+    /// Serve the embedded file named `name` when the requested path has no matching entry,
+    /// instead of responding `404`. Useful for single-page apps routing to `index.html`.
+    pub fn with_fallback(mut self, name: impl Into<String>) -> Self {
+        self.fallback = Some(name.into());
+        self
+    }
+
+    /// Run `handler` when the requested path has no matching entry (and no [`with_fallback`]
+    /// file matches either), instead of responding `404`. The handler receives the original
+    /// request unchanged, so it can inspect the attempted path.
+    ///
+    /// [`with_fallback`]: StaticEmbed::with_fallback
+    pub fn with_fallback_handler(mut self, handler: impl Handler) -> Self {
+        self.fallback_handler = Some(Arc::new(handler));
+        self
+    }
+}
+
+#[async_trait]
+impl<E: RustEmbed + Send + Sync + 'static> Handler for StaticEmbed<E> {
+    async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+        let rel_path = req.param::<String>("**path").unwrap_or_default();
+        let rel_path = format_url_path_safely(&rel_path);
+
+        if let Some(file) = E::get(&rel_path) {
+            render_embedded_file(file, req, res);
+            return;
+        }
+        if let Some(fallback) = &self.fallback {
+            if let Some(file) = E::get(fallback) {
+                render_embedded_file(file, req, res);
+                return;
+            }
+        }
+        match &self.fallback_handler {
+            Some(handler) => handler.handle(req, depot, res, ctrl).await,
+            None => res.render(StatusError::not_found()),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! negotiation helpers for serving precompressed static asset variants
+
+use std::path::{Path, PathBuf};
+
+use salvo_core::http::header::ACCEPT_ENCODING;
+use salvo_core::http::Request;
+
+/// A content-coding that a precompressed sibling file may be stored under.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ContentCoding {
+    /// Brotli, stored as `<name>.br`.
+    Brotli,
+    /// Gzip, stored as `<name>.gz`.
+    Gzip,
+    /// Zstandard, stored as `<name>.zst`.
+    Zstd,
+}
+
+impl ContentCoding {
+    /// File extension appended to the original file name for this coding.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Brotli => "br",
+            Self::Gzip => "gz",
+            Self::Zstd => "zst",
+        }
+    }
+
+    /// Value to use in the `Content-Encoding` response header.
+    pub fn header_value(self) -> &'static str {
+        match self {
+            Self::Brotli => "br",
+            Self::Gzip => "gzip",
+            Self::Zstd => "zstd",
+        }
+    }
+
+    fn from_token(token: &str) -> Option<Self> {
+        match token {
+            "br" => Some(Self::Brotli),
+            "gzip" | "x-gzip" => Some(Self::Gzip),
+            "zstd" => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+}
+
+/// Parse an `Accept-Encoding` header value into `(coding, q)` pairs, sorted by descending `q`.
+///
+/// Each comma-separated entry is split on `;q=`; a missing `q` defaults to `1.0` and entries
+/// with `q=0` (or an encoding we don't recognize) are dropped.
+pub(crate) fn parse_accept_encoding(header: &str) -> Vec<(ContentCoding, f32)> {
+    let mut codings: Vec<(ContentCoding, f32)> = header
+        .split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.splitn(2, ";q=");
+            let coding = ContentCoding::from_token(parts.next()?.trim())?;
+            let q = parts
+                .next()
+                .and_then(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            (q > 0.0).then_some((coding, q))
+        })
+        .collect();
+    codings.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    codings
+}
+
+/// The on-disk path of `path`'s precompressed sibling for `coding` (e.g. `app.js` -> `app.js.br`).
+pub(crate) fn sibling_path(path: &Path, coding: ContentCoding) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(coding.extension());
+    PathBuf::from(name)
+}
+
+/// Resolve which file should actually be served for `path`, given the encodings enabled for it
+/// and the request's `Accept-Encoding` header. Falls back to the identity file when `enabled`
+/// is empty, the header is absent, or no enabled sibling exists on disk.
+pub(crate) fn resolve_precompressed(
+    path: &Path,
+    enabled: &[ContentCoding],
+    req: &Request,
+) -> (PathBuf, Option<ContentCoding>) {
+    if enabled.is_empty() {
+        return (path.to_owned(), None);
+    }
+    let accepted = req
+        .headers()
+        .get(ACCEPT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .map(parse_accept_encoding)
+        .unwrap_or_default();
+    let coding = accepted
+        .into_iter()
+        .map(|(coding, _)| coding)
+        .filter(|coding| enabled.contains(coding))
+        .find(|coding| sibling_path(path, *coding).is_file());
+    match coding {
+        Some(coding) => (sibling_path(path, coding), Some(coding)),
+        None => (path.to_owned(), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_missing_q_to_one() {
+        assert_eq!(parse_accept_encoding("br"), vec![(ContentCoding::Brotli, 1.0)]);
+    }
+
+    #[test]
+    fn drops_q_zero_and_unknown_codings() {
+        let codings = parse_accept_encoding("br;q=0, gzip, identity;q=0.5, deflate");
+        assert_eq!(codings, vec![(ContentCoding::Gzip, 1.0)]);
+    }
+
+    #[test]
+    fn sorts_by_descending_q() {
+        let codings = parse_accept_encoding("br;q=0.2, gzip;q=0.8, zstd;q=0.5");
+        assert_eq!(
+            codings,
+            vec![
+                (ContentCoding::Gzip, 0.8),
+                (ContentCoding::Zstd, 0.5),
+                (ContentCoding::Brotli, 0.2),
+            ]
+        );
+    }
+
+    #[test]
+    fn accepts_gzip_aliases() {
+        assert_eq!(parse_accept_encoding("x-gzip"), vec![(ContentCoding::Gzip, 1.0)]);
+    }
+
+    #[test]
+    fn empty_header_yields_no_codings() {
+        assert!(parse_accept_encoding("").is_empty());
+    }
+}
@@ -0,0 +1,253 @@
+//! static file handler
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use mime_guess::MimeGuess;
+use salvo_core::http::header::{
+    ACCEPT_RANGES, CONTENT_DISPOSITION, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, ETAG, IF_RANGE,
+    LAST_MODIFIED, RANGE, VARY,
+};
+use salvo_core::http::{HeaderValue, StatusCode, StatusError};
+use salvo_core::{async_trait, Depot, FlowCtrl, Handler, Request, Response};
+use tokio::fs::File;
+use tokio::io::AsyncSeekExt;
+use tokio_util::io::ReaderStream;
+
+use super::conditional::{self, Precondition};
+use super::disposition::{self, ContentDisposition};
+use super::encoding::{resolve_precompressed, ContentCoding};
+use super::range::HttpRange;
+
+/// `StaticFile` is a handler that serves a single file from the file system.
+#[derive(Clone, Debug)]
+pub struct StaticFile {
+    path: PathBuf,
+    chunk_size: Option<u64>,
+    precompressed: Vec<ContentCoding>,
+    disposition: ContentDisposition,
+    download_name: Option<String>,
+}
+
+impl StaticFile {
+    /// Create a new `StaticFile` that serves the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            chunk_size: None,
+            precompressed: Vec::new(),
+            disposition: ContentDisposition::Inline,
+            download_name: None,
+        }
+    }
+
+    /// Set the chunk size used when streaming the file body.
+    pub fn chunk_size(mut self, chunk_size: u64) -> Self {
+        self.chunk_size = Some(chunk_size);
+        self
+    }
+
+    /// Enable serving precompressed sibling files (`<path>.br`, `<path>.gz`, `<path>.zst`, ...)
+    /// when the request's `Accept-Encoding` header allows it and the sibling exists on disk.
+    pub fn precompressed(mut self, codings: impl IntoIterator<Item = ContentCoding>) -> Self {
+        self.precompressed.extend(codings);
+        self
+    }
+
+    /// Respond with `Content-Disposition: attachment`, so browsers download the file instead of
+    /// rendering it.
+    pub fn content_disposition_attachment(mut self) -> Self {
+        self.disposition = ContentDisposition::Attachment;
+        self
+    }
+
+    /// Respond with `Content-Disposition: inline` (the default), so browsers render the file
+    /// when they can.
+    pub fn content_disposition_inline(mut self) -> Self {
+        self.disposition = ContentDisposition::Inline;
+        self
+    }
+
+    /// Override the file name advertised in `Content-Disposition`. Defaults to the served path's
+    /// own file name.
+    pub fn download_name(mut self, name: impl Into<String>) -> Self {
+        self.download_name = Some(name.into());
+        self
+    }
+}
+
+#[async_trait]
+impl Handler for StaticFile {
+    async fn handle(&self, req: &mut Request, _depot: &mut Depot, res: &mut Response, _ctrl: &mut FlowCtrl) {
+        let (serve_path, coding) = resolve_precompressed(&self.path, &self.precompressed, req);
+        let name = self
+            .download_name
+            .clone()
+            .or_else(|| self.path.file_name().map(|name| name.to_string_lossy().into_owned()));
+        send_file(
+            &self.path,
+            &serve_path,
+            coding,
+            !self.precompressed.is_empty(),
+            self.chunk_size,
+            Some((self.disposition, name)),
+            req,
+            res,
+        )
+        .await;
+    }
+}
+
+/// Whether the `If-Range` header value still matches the resource, meaning the requested
+/// `Range` should be honored. An unparseable or absent `If-Range` always honors the range.
+///
+/// Per RFC 7233 §3.2, a weak validator must never be used to answer `If-Range`: a weak `ETag`
+/// (ours included) can match a resource that changed in ways the validator doesn't track, which
+/// would let a range request splice stale and fresh bytes together. So an `If-Range` carrying a
+/// weak validator never matches, and neither does one compared against our own weak `etag`; in
+/// both cases we fall back to a full `200` response instead of `206`.
+pub(crate) fn if_range_matches(if_range: &str, etag: &str, modified: Option<SystemTime>) -> bool {
+    if if_range.starts_with("W/\"") {
+        false
+    } else if if_range.starts_with('"') {
+        !etag.starts_with("W/\"") && if_range == etag
+    } else if let Some(modified) = modified {
+        match httpdate::parse_http_date(if_range) {
+            Ok(date) => date >= conditional::truncate_to_secs(modified),
+            Err(_) => true,
+        }
+    } else {
+        true
+    }
+}
+
+/// Stream `serve_path` to `res`, guessing the `Content-Type` from `original_path`'s extension
+/// (so a precompressed sibling like `app.js.br` is still reported as `text/javascript`),
+/// evaluating conditional (`ETag`/`Last-Modified`) headers, and honoring a `Range` request
+/// (with `If-Range`) against the served file.
+pub(crate) async fn send_file(
+    original_path: &Path,
+    serve_path: &Path,
+    coding: Option<ContentCoding>,
+    precompressed_enabled: bool,
+    chunk_size: Option<u64>,
+    disposition: Option<(ContentDisposition, Option<String>)>,
+    req: &mut Request,
+    res: &mut Response,
+) {
+    if !serve_path.is_file() {
+        res.render(StatusError::not_found());
+        return;
+    }
+    let mut file = match File::open(serve_path).await {
+        Ok(file) => file,
+        Err(_) => {
+            res.render(StatusError::not_found());
+            return;
+        }
+    };
+    let metadata = match file.metadata().await {
+        Ok(metadata) => metadata,
+        Err(_) => {
+            res.render(StatusError::internal_server_error());
+            return;
+        }
+    };
+    let full_len = metadata.len();
+    let modified = metadata.modified().ok();
+    let etag = conditional::etag(full_len, modified);
+
+    let headers = res.headers_mut();
+    headers.insert(ETAG, HeaderValue::from_str(&etag).unwrap());
+    if let Some(modified) = modified {
+        headers.insert(
+            LAST_MODIFIED,
+            HeaderValue::from_str(&httpdate::fmt_http_date(modified)).unwrap(),
+        );
+    }
+
+    match conditional::evaluate(req, &etag, modified) {
+        Precondition::Failed => {
+            res.status_code(StatusCode::PRECONDITION_FAILED);
+            return;
+        }
+        Precondition::NotModified => {
+            res.status_code(StatusCode::NOT_MODIFIED);
+            return;
+        }
+        Precondition::Proceed => {}
+    }
+
+    let mime = MimeGuess::from_path(original_path).first_or_octet_stream();
+    let headers = res.headers_mut();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_str(mime.as_ref()).unwrap());
+    headers.insert(ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    if let Some((disposition, name)) = &disposition {
+        if let Ok(value) = HeaderValue::from_str(&disposition::header_value(*disposition, name.as_deref())) {
+            headers.insert(CONTENT_DISPOSITION, value);
+        }
+    }
+    if let Some(coding) = coding {
+        headers.insert(CONTENT_ENCODING, HeaderValue::from_static(coding.header_value()));
+    }
+    if precompressed_enabled {
+        // The response body depends on Accept-Encoding even when the identity file is served
+        // (e.g. the client doesn't accept any enabled coding, or no sibling exists on disk), so
+        // a shared cache must not serve this response to a request with a different one.
+        headers.insert(VARY, HeaderValue::from_static("Accept-Encoding"));
+    }
+
+    let range_header = req
+        .headers()
+        .get(RANGE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_owned);
+    let honor_range = range_header.is_some()
+        && req
+            .headers()
+            .get(IF_RANGE)
+            .and_then(|value| value.to_str().ok())
+            .map(|if_range| if_range_matches(if_range, &etag, modified))
+            .unwrap_or(true);
+
+    let range = match (honor_range, range_header) {
+        (true, Some(header)) => match HttpRange::parse(&header, full_len) {
+            Ok(ranges) => Some(ranges[0]),
+            Err(_) => {
+                res.headers_mut().insert(
+                    CONTENT_RANGE,
+                    HeaderValue::from_str(&format!("bytes */{full_len}")).unwrap(),
+                );
+                res.status_code(StatusCode::RANGE_NOT_SATISFIABLE);
+                return;
+            }
+        },
+        _ => None,
+    };
+
+    let stream = if let Some(range) = range {
+        if file.seek(std::io::SeekFrom::Start(range.start)).await.is_err() {
+            res.render(StatusError::internal_server_error());
+            return;
+        }
+        let headers = res.headers_mut();
+        headers.insert(
+            CONTENT_RANGE,
+            HeaderValue::from_str(&format!("bytes {}-{}/{full_len}", range.start, range.start + range.length - 1))
+                .unwrap(),
+        );
+        headers.insert(CONTENT_LENGTH, HeaderValue::from_str(&range.length.to_string()).unwrap());
+        res.status_code(StatusCode::PARTIAL_CONTENT);
+        ReaderStream::new(tokio::io::AsyncReadExt::take(file, range.length))
+    } else {
+        res.headers_mut()
+            .insert(CONTENT_LENGTH, HeaderValue::from_str(&full_len.to_string()).unwrap());
+        match chunk_size {
+            Some(chunk_size) => {
+                ReaderStream::with_capacity(tokio::io::AsyncReadExt::take(file, full_len), chunk_size as usize)
+            }
+            None => ReaderStream::new(tokio::io::AsyncReadExt::take(file, full_len)),
+        }
+    };
+    res.stream(stream);
+}
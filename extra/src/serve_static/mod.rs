@@ -1,13 +1,21 @@
 //! serve static dir and file middleware
 
+mod archive;
+mod conditional;
 pub mod dir;
+mod disposition;
 mod embed;
+mod encoding;
 mod file;
+mod range;
 
 use percent_encoding::{utf8_percent_encode, CONTROLS};
 
+pub use archive::StaticArchive;
 pub use dir::{StaticDir, StaticDirOptions};
+pub use disposition::ContentDisposition;
 pub use embed::{render_embedded_file, static_embed, EmbeddedFileExt, StaticEmbed};
+pub use encoding::ContentCoding;
 pub use file::StaticFile;
 
 #[inline]
@@ -127,6 +135,179 @@ mod tests {
         assert_eq!(response.status_code().unwrap(), StatusCode::NOT_FOUND);
     }
 
+    #[tokio::test]
+    async fn test_serve_static_dir_fallback_handler() {
+        #[handler]
+        async fn fallback(res: &mut Response) {
+            res.render("fell back");
+        }
+
+        let router = Router::with_path("<**path>").get(
+            StaticDir::width_options(
+                vec!["test/static"],
+                StaticDirOptions {
+                    dot_files: false,
+                    listing: false,
+                    defaults: vec!["index.html".to_owned()],
+                },
+            )
+            .with_fallback_handler(fallback),
+        );
+        let service = Service::new(router);
+
+        let mut response = TestClient::get("http://127.0.0.1:7979/notexist.txt")
+            .send(&service)
+            .await;
+        assert_eq!(response.status_code().unwrap(), StatusCode::OK);
+        assert_eq!(response.take_string().await.unwrap(), "fell back");
+    }
+
+    #[tokio::test]
+    async fn test_serve_static_file_content_disposition() {
+        let router = Router::with_path("test1.txt").get(
+            StaticFile::new("test/static/test1.txt")
+                .content_disposition_attachment()
+                .download_name("evil\r\nname.txt"),
+        );
+        let service = Service::new(router);
+
+        let response = TestClient::get("http://127.0.0.1:7979/test1.txt")
+            .send(&service)
+            .await;
+        assert_eq!(response.status_code().unwrap(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get("content-disposition").unwrap(),
+            "attachment; filename=\"evil__name.txt\""
+        );
+    }
+
+    #[tokio::test]
+    async fn test_serve_static_archive() {
+        use std::io::Write as _;
+
+        use zip::write::FileOptions;
+        use zip::ZipWriter;
+
+        let mut buf = Vec::new();
+        {
+            let mut writer = ZipWriter::new(std::io::Cursor::new(&mut buf));
+            writer.start_file("index.html", FileOptions::default()).unwrap();
+            writer.write_all(b"<html>Archive Index</html>").unwrap();
+            writer.start_file("dir1/test.txt", FileOptions::default()).unwrap();
+            writer.write_all(b"nested file").unwrap();
+            writer.finish().unwrap();
+        }
+
+        let router = Router::with_path("<**path>").get(StaticArchive::from_bytes(buf).unwrap());
+        let service = Service::new(router);
+
+        let mut response = TestClient::get("http://127.0.0.1:7979/").send(&service).await;
+        assert_eq!(response.status_code().unwrap(), StatusCode::OK);
+        assert_eq!(response.take_string().await.unwrap(), "<html>Archive Index</html>");
+
+        let mut response = TestClient::get("http://127.0.0.1:7979/dir1/test.txt")
+            .send(&service)
+            .await;
+        assert_eq!(response.status_code().unwrap(), StatusCode::OK);
+        assert_eq!(response.take_string().await.unwrap(), "nested file");
+
+        let response = TestClient::get("http://127.0.0.1:7979/missing.txt")
+            .send(&service)
+            .await;
+        assert_eq!(response.status_code().unwrap(), StatusCode::NOT_FOUND);
+
+        // Serving two entries concurrently must not corrupt the shared parsed archive.
+        let (a, b) = tokio::join!(
+            TestClient::get("http://127.0.0.1:7979/index.html").send(&service),
+            TestClient::get("http://127.0.0.1:7979/dir1/test.txt").send(&service),
+        );
+        assert_eq!(a.status_code().unwrap(), StatusCode::OK);
+        assert_eq!(b.status_code().unwrap(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_serve_static_file_precompressed() {
+        let router = Router::with_path("test1.txt").get(
+            StaticFile::new("test/static/test1.txt").precompressed(vec![ContentCoding::Gzip]),
+        );
+        let service = Service::new(router);
+
+        // A sibling `test1.txt.gz` exists on disk and the client accepts gzip: served
+        // compressed, with both `Content-Encoding` and `Vary` set.
+        let response = TestClient::get("http://127.0.0.1:7979/test1.txt")
+            .add_header("accept-encoding", "gzip", true)
+            .send(&service)
+            .await;
+        assert_eq!(response.status_code().unwrap(), StatusCode::OK);
+        assert_eq!(response.headers().get("content-encoding").unwrap(), "gzip");
+        assert_eq!(response.headers().get("vary").unwrap(), "Accept-Encoding");
+
+        // The client doesn't accept gzip, so the identity file is served instead — but `Vary`
+        // must still be set, since a cache keyed only on the URL would otherwise serve this
+        // identity body to a later request that does accept gzip.
+        let response = TestClient::get("http://127.0.0.1:7979/test1.txt").send(&service).await;
+        assert_eq!(response.status_code().unwrap(), StatusCode::OK);
+        assert!(response.headers().get("content-encoding").is_none());
+        assert_eq!(response.headers().get("vary").unwrap(), "Accept-Encoding");
+    }
+
+    #[tokio::test]
+    async fn test_serve_static_file_range() {
+        let router = Router::with_path("test1.txt").get(StaticFile::new("test/static/test1.txt"));
+        let service = Service::new(router);
+
+        let mut response = TestClient::get("http://127.0.0.1:7979/test1.txt")
+            .add_header("range", "bytes=0-1", true)
+            .send(&service)
+            .await;
+        assert_eq!(response.status_code().unwrap(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.take_string().await.unwrap(), "co");
+
+        let response = TestClient::get("http://127.0.0.1:7979/test1.txt")
+            .add_header("range", "bytes=100-200", true)
+            .send(&service)
+            .await;
+        assert_eq!(response.status_code().unwrap(), StatusCode::RANGE_NOT_SATISFIABLE);
+
+        // `StaticFile` only has a weak ETag, so `If-Range` must never be honored with it,
+        // regardless of what the client sends; the range request falls back to a full response.
+        let mut response = TestClient::get("http://127.0.0.1:7979/test1.txt")
+            .add_header("range", "bytes=0-1", true)
+            .add_header("if-range", "\"some-etag\"", true)
+            .send(&service)
+            .await;
+        assert_eq!(response.status_code().unwrap(), StatusCode::OK);
+        assert_eq!(response.take_string().await.unwrap(), "copy1");
+    }
+
+    #[tokio::test]
+    async fn test_serve_static_file_conditional() {
+        let router = Router::with_path("test1.txt").get(StaticFile::new("test/static/test1.txt"));
+        let service = Service::new(router);
+
+        let mut response = TestClient::get("http://127.0.0.1:7979/test1.txt").send(&service).await;
+        assert_eq!(response.status_code().unwrap(), StatusCode::OK);
+        let etag = response.headers().get("etag").unwrap().to_str().unwrap().to_owned();
+
+        let response = TestClient::get("http://127.0.0.1:7979/test1.txt")
+            .add_header("if-none-match", &etag, true)
+            .send(&service)
+            .await;
+        assert_eq!(response.status_code().unwrap(), StatusCode::NOT_MODIFIED);
+
+        let response = TestClient::get("http://127.0.0.1:7979/test1.txt")
+            .add_header("if-match", "\"not-the-etag\"", true)
+            .send(&service)
+            .await;
+        assert_eq!(response.status_code().unwrap(), StatusCode::PRECONDITION_FAILED);
+
+        let response = TestClient::get("http://127.0.0.1:7979/test1.txt")
+            .add_header("if-match", &etag, true)
+            .send(&service)
+            .await;
+        assert_eq!(response.status_code().unwrap(), StatusCode::OK);
+    }
+
     #[tokio::test]
     async fn test_serve_embed_files() {
         #[derive(RustEmbed)]
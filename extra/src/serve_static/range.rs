@@ -0,0 +1,134 @@
+//! `Range` header parsing, modeled on actix-web's `HttpRange`.
+
+/// A single byte range, already resolved and clamped against the full resource length.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct HttpRange {
+    pub start: u64,
+    pub length: u64,
+}
+
+impl HttpRange {
+    /// Parse a `Range: bytes=...` header value against a resource of `full_len` bytes.
+    ///
+    /// Accepts `bytes=start-end`, `bytes=start-` and `bytes=-suffix_len` (and a comma-separated
+    /// list of those). Returns `Err(())` when the header is malformed or none of the requested
+    /// ranges can be satisfied, in which case the caller should respond `416 Range Not
+    /// Satisfiable`.
+    pub(crate) fn parse(header: &str, full_len: u64) -> Result<Vec<Self>, ()> {
+        let spec = header.trim().strip_prefix("bytes=").ok_or(())?;
+
+        let mut ranges = Vec::new();
+        for part in spec.split(',') {
+            let part = part.trim();
+            if part.is_empty() {
+                continue;
+            }
+            let (start, end) = part.split_once('-').ok_or(())?;
+
+            if start.is_empty() {
+                // bytes=-suffix_len : the last `suffix_len` bytes of the resource.
+                let suffix_len: u64 = end.parse().map_err(|_| ())?;
+                let suffix_len = suffix_len.min(full_len);
+                if suffix_len == 0 {
+                    continue;
+                }
+                ranges.push(Self {
+                    start: full_len - suffix_len,
+                    length: suffix_len,
+                });
+            } else {
+                let start: u64 = start.parse().map_err(|_| ())?;
+                if start >= full_len {
+                    continue;
+                }
+                let end: u64 = if end.is_empty() {
+                    full_len - 1
+                } else {
+                    end.parse::<u64>().map_err(|_| ())?.min(full_len - 1)
+                };
+                if end < start {
+                    return Err(());
+                }
+                ranges.push(Self {
+                    start,
+                    length: end - start + 1,
+                });
+            }
+        }
+
+        if ranges.is_empty() {
+            Err(())
+        } else {
+            Ok(ranges)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HttpRange;
+
+    #[test]
+    fn parses_start_end() {
+        let ranges = HttpRange::parse("bytes=2-5", 10).unwrap();
+        assert_eq!(ranges, vec![HttpRange { start: 2, length: 4 }]);
+    }
+
+    #[test]
+    fn parses_open_ended() {
+        let ranges = HttpRange::parse("bytes=5-", 10).unwrap();
+        assert_eq!(ranges, vec![HttpRange { start: 5, length: 5 }]);
+    }
+
+    #[test]
+    fn parses_suffix() {
+        let ranges = HttpRange::parse("bytes=-5", 10).unwrap();
+        assert_eq!(ranges, vec![HttpRange { start: 5, length: 5 }]);
+    }
+
+    #[test]
+    fn clamps_suffix_longer_than_resource() {
+        let ranges = HttpRange::parse("bytes=-50", 10).unwrap();
+        assert_eq!(ranges, vec![HttpRange { start: 0, length: 10 }]);
+    }
+
+    #[test]
+    fn clamps_end_past_resource() {
+        let ranges = HttpRange::parse("bytes=5-100", 10).unwrap();
+        assert_eq!(ranges, vec![HttpRange { start: 5, length: 5 }]);
+    }
+
+    #[test]
+    fn rejects_end_before_start() {
+        assert_eq!(HttpRange::parse("bytes=5-2", 10), Err(()));
+    }
+
+    #[test]
+    fn rejects_start_at_or_past_resource() {
+        assert_eq!(HttpRange::parse("bytes=10-", 10), Err(()));
+    }
+
+    #[test]
+    fn rejects_zero_suffix() {
+        assert_eq!(HttpRange::parse("bytes=-0", 10), Err(()));
+    }
+
+    #[test]
+    fn rejects_missing_unit() {
+        assert_eq!(HttpRange::parse("2-5", 10), Err(()));
+    }
+
+    #[test]
+    fn rejects_malformed_part() {
+        assert_eq!(HttpRange::parse("bytes=abc-5", 10), Err(()));
+    }
+
+    #[test]
+    fn parses_comma_separated_list() {
+        let ranges = HttpRange::parse("bytes=0-1,3-4", 10).unwrap();
+        assert_eq!(
+            ranges,
+            vec![HttpRange { start: 0, length: 2 }, HttpRange { start: 3, length: 2 }]
+        );
+    }
+}